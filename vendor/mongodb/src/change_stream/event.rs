@@ -6,7 +6,7 @@ use crate::{cursor::CursorSpecification, options::ChangeStreamOptions};
 
 #[cfg(test)]
 use bson::Bson;
-use bson::{Document, RawBson, RawDocumentBuf, Timestamp};
+use bson::{DateTime, Document, RawBson, RawDocumentBuf, Timestamp};
 use serde::{Deserialize, Serialize};
 
 /// An opaque token used for resuming an interrupted
@@ -48,6 +48,112 @@ impl ResumeToken {
     }
 }
 
+/// Buffers the fragments of a change event that the server split via
+/// `$changeStreamSplitLargeEvent`, merging them into a single document once every fragment has
+/// arrived.
+///
+/// Fragments are split on top-level field boundaries, so merging is a field-by-field union of the
+/// raw fragment documents. Fragments must arrive contiguously and in order (`1..=of`); a gap or
+/// out-of-order fragment surfaces an error rather than silently dropping data.
+///
+/// Driven by [`next_change_stream_event`], which the `ChangeStream` cursor's batch-consuming loop
+/// calls once per raw document it reads off the wire.
+#[derive(Debug)]
+pub(crate) struct SplitEventBuffer {
+    of: Option<i32>,
+    next_fragment: i32,
+    merged: Document,
+}
+
+impl SplitEventBuffer {
+    pub(crate) fn new() -> Self {
+        Self {
+            of: None,
+            next_fragment: 1,
+            merged: Document::new(),
+        }
+    }
+
+    /// Adds a fragment to the buffer. Returns the merged document once the final fragment has
+    /// been added, or `Ok(None)` if more fragments are still expected for this split event.
+    pub(crate) fn push(
+        &mut self,
+        split: SplitEvent,
+        fragment: Document,
+    ) -> crate::error::Result<Option<Document>> {
+        if let Some(of) = self.of {
+            if split.of != of {
+                return Err(crate::error::Error::invalid_response(format!(
+                    "expected {} fragments for this split change event, got {}",
+                    of, split.of
+                )));
+            }
+        } else {
+            self.of = Some(split.of);
+        }
+
+        if split.fragment != self.next_fragment {
+            return Err(crate::error::Error::invalid_response(format!(
+                "expected fragment {} of split change event, got fragment {}",
+                self.next_fragment, split.fragment
+            )));
+        }
+
+        self.merged.extend(fragment);
+        self.next_fragment += 1;
+
+        if split.fragment == split.of {
+            self.of = None;
+            self.next_fragment = 1;
+            Ok(Some(std::mem::take(&mut self.merged)))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// Feeds one raw document read off the change stream's underlying batch cursor through `buffer`,
+/// returning the next event to yield to the caller.
+///
+/// Returns `Ok(None)` if `raw` was a fragment of a split event and more fragments are still
+/// expected; the cursor should request its next document without yielding anything. Returns
+/// `Ok(Some(event))` once a complete event is available, either because `raw` was not split in
+/// the first place or because it was the final fragment of one. The resume token on a merged
+/// event is always the token embedded in the last fragment, since resuming from an earlier
+/// fragment would replay the whole split event.
+///
+/// This is the reassembly routine itself; the batch cursor that supplies `raw` by reading batches
+/// off the wire lives in the rest of the `ChangeStream` implementation.
+pub(crate) fn next_change_stream_event<T>(
+    buffer: &mut SplitEventBuffer,
+    raw: Document,
+) -> crate::error::Result<Option<ChangeStreamEvent<T>>>
+where
+    T: serde::de::DeserializeOwned,
+{
+    let split = raw
+        .get("splitEvent")
+        .map(|value| bson::from_bson::<SplitEvent>(value.clone()))
+        .transpose()
+        .map_err(|e| crate::error::Error::invalid_response(e.to_string()))?;
+
+    let event_doc = match split {
+        Some(split) => match buffer.push(split, raw)? {
+            Some(mut merged) => {
+                // The merged document still carries the last fragment's `splitEvent`.
+                merged.remove("splitEvent");
+                merged
+            }
+            None => return Ok(None),
+        },
+        None => raw,
+    };
+
+    let event = bson::from_document(event_doc)
+        .map_err(|e| crate::error::Error::invalid_response(e.to_string()))?;
+    Ok(Some(event))
+}
+
 /// A `ChangeStreamEvent` represents a
 /// [change event](https://docs.mongodb.com/manual/reference/change-events/) in the associated change stream.
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
@@ -87,6 +193,10 @@ pub struct ChangeStreamEvent<T> {
     /// The cluster time at which the change occurred.
     pub cluster_time: Option<Timestamp>,
 
+    /// The server-clock time at which the change occurred, as opposed to the cluster time, which
+    /// is derived from the oplog entry associated with the change.
+    pub wall_time: Option<DateTime>,
+
     /// The `Document` created or modified by the `insert`, `replace`, `delete`, `update`
     /// operations (i.e. CRUD operations).
     ///
@@ -99,6 +209,45 @@ pub struct ChangeStreamEvent<T> {
     /// represents the most current majority-committed version of the document modified by the
     /// update operation.
     pub full_document: Option<T>,
+
+    /// The document before the change, when available.
+    ///
+    /// This is only populated if the collection has `changeStreamPreAndPostImages` enabled and the
+    /// change stream was configured with
+    /// [`full_document_before_change`](crate::options::ChangeStreamOptions::full_document_before_change)
+    /// set to something other than
+    /// [`Off`](crate::options::FullDocumentBeforeChangeType::Off). For `delete` operations, this
+    /// field contains the deleted document, which is otherwise unavailable.
+    pub full_document_before_change: Option<T>,
+
+    /// Additional information about the event that is specific to the `operation_type`.
+    ///
+    /// This is populated for the expanded DDL/sharding events surfaced when a change stream is
+    /// opened with `showExpandedEvents`, e.g. the index specifications for
+    /// `OperationType::CreateIndexes` or the new shard key for
+    /// `OperationType::ShardCollection`.
+    pub operation_description: Option<Document>,
+
+    /// Present on the raw fragments of a change event that was split by the server's
+    /// `$changeStreamSplitLargeEvent` stage because it exceeded the 16MB BSON document limit.
+    ///
+    /// A fully reassembled `ChangeStreamEvent` never has this field set. Raw fragments carrying
+    /// this field are merged by [`next_change_stream_event`] and are never deserialized into a
+    /// `ChangeStreamEvent` directly, so in practice this field is never observed as `Some` on a
+    /// value of this type; it exists so the raw fragment document can round-trip through
+    /// (de)serialization while it's being buffered.
+    pub split_event: Option<SplitEvent>,
+}
+
+/// Identifies one fragment of a change event that the server split because it was larger than
+/// the 16MB BSON document limit.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SplitEvent {
+    /// The 1-based index of this fragment.
+    pub fragment: i32,
+
+    /// The total number of fragments that make up the complete event.
+    pub of: i32,
 }
 
 /// Describes which fields have been updated or removed from a document.
@@ -130,8 +279,12 @@ pub struct TruncatedArray {
 }
 
 /// The operation type represented in a given change notification.
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
-#[serde(rename_all = "camelCase")]
+///
+/// This enum is `#[non_exhaustive]` and deserializes any `operationType` string it doesn't
+/// recognize into [`OperationType::Other`] rather than failing, so a change stream keeps working
+/// across server upgrades that introduce new event types. Callers should always include a
+/// wildcard arm (e.g. `_ => ...`) when matching on this enum.
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum OperationType {
     /// See [insert-event](https://docs.mongodb.com/manual/reference/change-events/#insert-event)
@@ -157,6 +310,107 @@ pub enum OperationType {
 
     /// See [invalidate-event](https://docs.mongodb.com/manual/reference/change-events/#invalidate-event)
     Invalidate,
+
+    /// See [create-event](https://docs.mongodb.com/manual/reference/change-events/#create-event).
+    /// Only included if the change stream was opened with `showExpandedEvents` enabled.
+    Create,
+
+    /// See [modify-event](https://docs.mongodb.com/manual/reference/change-events/#modify-event).
+    /// Only included if the change stream was opened with `showExpandedEvents` enabled.
+    Modify,
+
+    /// See
+    /// [createindexes-event](https://docs.mongodb.com/manual/reference/change-events/#createindexes-event).
+    /// Only included if the change stream was opened with `showExpandedEvents` enabled.
+    CreateIndexes,
+
+    /// See
+    /// [dropindexes-event](https://docs.mongodb.com/manual/reference/change-events/#dropindexes-event).
+    /// Only included if the change stream was opened with `showExpandedEvents` enabled.
+    DropIndexes,
+
+    /// See
+    /// [shardcollection-event](https://docs.mongodb.com/manual/reference/change-events/#shardcollection-event).
+    /// Only included if the change stream was opened with `showExpandedEvents` enabled.
+    ShardCollection,
+
+    /// See
+    /// [refinecollectionshardkey-event](https://docs.mongodb.com/manual/reference/change-events/#refinecollectionshardkey-event).
+    /// Only included if the change stream was opened with `showExpandedEvents` enabled.
+    RefineCollectionShardKey,
+
+    /// See
+    /// [reshardcollection-event](https://docs.mongodb.com/manual/reference/change-events/#reshardcollection-event).
+    /// Only included if the change stream was opened with `showExpandedEvents` enabled.
+    ReshardCollection,
+
+    /// Catch-all for an `operationType` string that this version of the driver does not
+    /// recognize, e.g. one introduced by a newer server version. The original string is
+    /// preserved so it round-trips through (de)serialization unchanged.
+    Other(String),
+}
+
+impl OperationType {
+    fn as_str(&self) -> &str {
+        match self {
+            OperationType::Insert => "insert",
+            OperationType::Update => "update",
+            OperationType::Replace => "replace",
+            OperationType::Delete => "delete",
+            OperationType::Drop => "drop",
+            OperationType::Rename => "rename",
+            OperationType::DropDatabase => "dropDatabase",
+            OperationType::Invalidate => "invalidate",
+            OperationType::Create => "create",
+            OperationType::Modify => "modify",
+            OperationType::CreateIndexes => "createIndexes",
+            OperationType::DropIndexes => "dropIndexes",
+            OperationType::ShardCollection => "shardCollection",
+            OperationType::RefineCollectionShardKey => "refineCollectionShardKey",
+            OperationType::ReshardCollection => "reshardCollection",
+            OperationType::Other(s) => s,
+        }
+    }
+
+    fn from_str(s: &str) -> Self {
+        match s {
+            "insert" => OperationType::Insert,
+            "update" => OperationType::Update,
+            "replace" => OperationType::Replace,
+            "delete" => OperationType::Delete,
+            "drop" => OperationType::Drop,
+            "rename" => OperationType::Rename,
+            "dropDatabase" => OperationType::DropDatabase,
+            "invalidate" => OperationType::Invalidate,
+            "create" => OperationType::Create,
+            "modify" => OperationType::Modify,
+            "createIndexes" => OperationType::CreateIndexes,
+            "dropIndexes" => OperationType::DropIndexes,
+            "shardCollection" => OperationType::ShardCollection,
+            "refineCollectionShardKey" => OperationType::RefineCollectionShardKey,
+            "reshardCollection" => OperationType::ReshardCollection,
+            other => OperationType::Other(other.to_string()),
+        }
+    }
+}
+
+impl Serialize for OperationType {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for OperationType {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Ok(OperationType::from_str(&s))
+    }
 }
 
 /// Identifies the collection or database on which an event occurred.
@@ -168,4 +422,59 @@ pub struct ChangeNamespace {
 
     /// The name of the collection in which the change occurred.
     pub coll: Option<String>,
+}
+
+#[cfg(test)]
+mod test {
+    use bson::doc;
+
+    use super::{SplitEvent, SplitEventBuffer};
+
+    #[test]
+    fn split_event_buffer_merges_fragments_in_order() {
+        let mut buffer = SplitEventBuffer::new();
+
+        let first = buffer
+            .push(SplitEvent { fragment: 1, of: 2 }, doc! { "a": 1 })
+            .unwrap();
+        assert_eq!(first, None);
+
+        let merged = buffer
+            .push(SplitEvent { fragment: 2, of: 2 }, doc! { "b": 2 })
+            .unwrap()
+            .expect("buffer should yield the merged document on the final fragment");
+        assert_eq!(merged, doc! { "a": 1, "b": 2 });
+    }
+
+    #[test]
+    fn split_event_buffer_errors_on_out_of_order_fragment() {
+        let mut buffer = SplitEventBuffer::new();
+        let err = buffer.push(SplitEvent { fragment: 2, of: 2 }, doc! { "b": 2 });
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn split_event_buffer_errors_on_fragment_count_mismatch() {
+        let mut buffer = SplitEventBuffer::new();
+        buffer
+            .push(SplitEvent { fragment: 1, of: 2 }, doc! { "a": 1 })
+            .unwrap();
+        let err = buffer.push(SplitEvent { fragment: 2, of: 3 }, doc! { "b": 2 });
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn split_event_buffer_reusable_after_a_complete_split() {
+        let mut buffer = SplitEventBuffer::new();
+        buffer
+            .push(SplitEvent { fragment: 1, of: 1 }, doc! { "a": 1 })
+            .unwrap()
+            .unwrap();
+
+        let merged = buffer
+            .push(SplitEvent { fragment: 1, of: 1 }, doc! { "b": 2 })
+            .unwrap()
+            .expect("buffer should accept a fresh split event after completing the last one");
+        assert_eq!(merged, doc! { "b": 2 });
+    }
 }
\ No newline at end of file
@@ -0,0 +1,74 @@
+//! Contains the options for creating a
+//! [`ChangeStream`](crate::change_stream::ChangeStream).
+use serde::{Deserialize, Serialize};
+use typed_builder::TypedBuilder;
+
+use crate::change_stream::event::ResumeToken;
+
+/// These are the valid options that can be passed to the `watch` method for creating a
+/// [`ChangeStream`](crate::change_stream::ChangeStream).
+#[derive(Clone, Debug, Default, Deserialize, Serialize, TypedBuilder)]
+#[builder(field_defaults(default, setter(into)))]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct ChangeStreamOptions {
+    /// Determines what to return for update operations.
+    pub full_document: Option<FullDocumentType>,
+
+    /// Determines what to return for the document that existed before an update, replace, or
+    /// delete event.
+    pub full_document_before_change: Option<FullDocumentBeforeChangeType>,
+
+    /// Specifies a resume token as a starting point for the change stream.
+    pub resume_after: Option<ResumeToken>,
+
+    /// Takes a resume token and starts a new change stream returning the first notification
+    /// after the token, including notifications for events that occurred before the token was
+    /// generated (e.g. collection drops). This option is mutually exclusive with `resume_after`.
+    pub start_after: Option<ResumeToken>,
+}
+
+/// Describes the modes for the `full_document` option of a
+/// [`ChangeStream`](crate::change_stream::ChangeStream).
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum FullDocumentType {
+    /// The `full_document` field of the `ChangeStreamEvent` will always contain the most current
+    /// majority-committed version of the document associated with the event.
+    UpdateLookup,
+
+    /// The `full_document` field of the `ChangeStreamEvent` will contain the post-image of the
+    /// modified document if available, without an additional lookup.
+    WhenAvailable,
+
+    /// The `full_document` field of the `ChangeStreamEvent` will contain the post-image of the
+    /// modified document if available, and the server will raise an error if it is not.
+    Required,
+
+    /// Default, no extra information is returned.
+    Default,
+}
+
+/// Describes the modes for the `full_document_before_change` option of a
+/// [`ChangeStream`](crate::change_stream::ChangeStream).
+///
+/// This is only meaningful for collections that have `changeStreamPreAndPostImages` enabled; it
+/// allows the pre-image of a modified document to be returned alongside the
+/// [`ChangeStreamEvent`](crate::change_stream::event::ChangeStreamEvent), most notably letting
+/// callers see what a `delete` removed.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum FullDocumentBeforeChangeType {
+    /// Do not include the pre-image of the modified document.
+    Off,
+
+    /// Include the pre-image of the modified document if it is available, without raising an
+    /// error if it is not.
+    WhenAvailable,
+
+    /// Include the pre-image of the modified document, and raise an error if it is not
+    /// available.
+    Required,
+}
@@ -0,0 +1,938 @@
+//! Contains the types and execution path for the cross-namespace `Client::bulk_write` API.
+use std::{collections::HashMap, ops::Range};
+
+use bson::{doc, oid::ObjectId, Bson, Document};
+
+use crate::{
+    coll::options::Namespace,
+    error::{Error, Result},
+    options::{Collation, Hint, UpdateModifications},
+};
+
+/// A single write to be performed as part of a [`Client::bulk_write`](crate::Client::bulk_write)
+/// call.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub enum WriteModel {
+    /// Insert a single document.
+    InsertOne {
+        /// The namespace to insert into.
+        namespace: Namespace,
+
+        /// The document to insert.
+        document: Document,
+    },
+
+    /// Update a single document matching `filter`.
+    UpdateOne {
+        /// The namespace to update in.
+        namespace: Namespace,
+
+        /// The filter selecting the document to update.
+        filter: Document,
+
+        /// The update to apply.
+        update: UpdateModifications,
+
+        /// A set of filters specifying which array elements an update should apply to, used with
+        /// positional `$` update operators.
+        array_filters: Option<Vec<Document>>,
+
+        /// The collation to use for string comparisons during the update.
+        collation: Option<Collation>,
+
+        /// The index to use for the update, either the index name as a string or the index spec
+        /// as a document.
+        hint: Option<Hint>,
+
+        /// Whether to insert a new document if none match `filter`.
+        upsert: Option<bool>,
+    },
+
+    /// Update all documents matching `filter`.
+    UpdateMany {
+        /// The namespace to update in.
+        namespace: Namespace,
+
+        /// The filter selecting the documents to update.
+        filter: Document,
+
+        /// The update to apply.
+        update: UpdateModifications,
+
+        /// A set of filters specifying which array elements an update should apply to, used with
+        /// positional `$` update operators.
+        array_filters: Option<Vec<Document>>,
+
+        /// The collation to use for string comparisons during the update.
+        collation: Option<Collation>,
+
+        /// The index to use for the update, either the index name as a string or the index spec
+        /// as a document.
+        hint: Option<Hint>,
+
+        /// Whether to insert a new document if none match `filter`.
+        upsert: Option<bool>,
+    },
+
+    /// Replace a single document matching `filter`.
+    ReplaceOne {
+        /// The namespace to replace in.
+        namespace: Namespace,
+
+        /// The filter selecting the document to replace.
+        filter: Document,
+
+        /// The replacement document.
+        replacement: Document,
+
+        /// The collation to use for string comparisons while matching `filter`.
+        collation: Option<Collation>,
+
+        /// The index to use for the replacement, either the index name as a string or the index
+        /// spec as a document.
+        hint: Option<Hint>,
+
+        /// Whether to insert `replacement` if no document matches `filter`.
+        upsert: Option<bool>,
+    },
+
+    /// Delete a single document matching `filter`.
+    DeleteOne {
+        /// The namespace to delete from.
+        namespace: Namespace,
+
+        /// The filter selecting the document to delete.
+        filter: Document,
+
+        /// The collation to use for string comparisons while matching `filter`.
+        collation: Option<Collation>,
+
+        /// The index to use for the delete, either the index name as a string or the index spec
+        /// as a document.
+        hint: Option<Hint>,
+    },
+
+    /// Delete all documents matching `filter`.
+    DeleteMany {
+        /// The namespace to delete from.
+        namespace: Namespace,
+
+        /// The filter selecting the documents to delete.
+        filter: Document,
+
+        /// The collation to use for string comparisons while matching `filter`.
+        collation: Option<Collation>,
+
+        /// The index to use for the delete, either the index name as a string or the index spec
+        /// as a document.
+        hint: Option<Hint>,
+    },
+}
+
+/// The options that can be used with [`Client::bulk_write`](crate::Client::bulk_write).
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct BulkWriteOptions {
+    /// Whether the writes should be executed in the order they were specified, stopping after
+    /// the first error if so. When `false`, writes are executed in arbitrary order and errors
+    /// from one write do not prevent the others from being attempted. Defaults to `true`.
+    pub ordered: Option<bool>,
+
+    /// Whether document-level validation should be bypassed for all writes in this call.
+    /// Defaults to `false`.
+    pub bypass_document_validation: Option<bool>,
+
+    /// A user-provided comment to attach to this command.
+    pub comment: Option<Bson>,
+
+    /// A map of parameter names and values, which can be accessed using `$$<name>` in filter and
+    /// update expressions.
+    pub let_vars: Option<Document>,
+
+    /// Whether this call should return per-operation results rather than only the aggregate
+    /// counts in [`SummaryBulkWriteResult`]. Defaults to `false`, since the server-side cost of
+    /// returning a result for every operation scales with the size of the batch.
+    pub verbose_results: Option<bool>,
+}
+
+/// The aggregate result of a [`Client::bulk_write`](crate::Client::bulk_write) call.
+///
+/// This is always populated. If
+/// [`verbose_results`](BulkWriteOptions::verbose_results) was set to `true`, the per-operation
+/// results are additionally available via the [`BulkWriteResult::Verbose`] variant.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[non_exhaustive]
+pub struct SummaryBulkWriteResult {
+    /// The number of documents inserted.
+    pub inserted_count: i64,
+
+    /// The number of documents matched by update/replace filters.
+    pub matched_count: i64,
+
+    /// The number of documents modified by update/replace operations.
+    pub modified_count: i64,
+
+    /// The number of documents deleted.
+    pub deleted_count: i64,
+
+    /// The number of documents upserted.
+    pub upserted_count: i64,
+
+    /// The `_id` of each document upserted in an update/replace operation, keyed by the index of
+    /// the corresponding [`WriteModel`] in the list of models passed to `bulk_write`.
+    pub upserted_ids: HashMap<usize, Bson>,
+}
+
+/// The result of a [`Client::bulk_write`](crate::Client::bulk_write) call made with
+/// [`verbose_results`](BulkWriteOptions::verbose_results) set to `true`.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[non_exhaustive]
+pub struct VerboseBulkWriteResult {
+    /// The aggregate counts across every operation in the batch.
+    pub summary: SummaryBulkWriteResult,
+
+    /// The result of each `InsertOne` model, keyed by its index in the original list of models.
+    pub insert_results: HashMap<usize, crate::results::InsertOneResult>,
+
+    /// The result of each update/replace model, keyed by its index in the original list of
+    /// models.
+    pub update_results: HashMap<usize, crate::results::UpdateResult>,
+
+    /// The result of each delete model, keyed by its index in the original list of models.
+    pub delete_results: HashMap<usize, crate::results::DeleteResult>,
+}
+
+/// The result of a successful [`Client::bulk_write`](crate::Client::bulk_write) call.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum BulkWriteResult {
+    /// Returned when [`verbose_results`](BulkWriteOptions::verbose_results) was not set to
+    /// `true`.
+    Summary(SummaryBulkWriteResult),
+
+    /// Returned when [`verbose_results`](BulkWriteOptions::verbose_results) was set to `true`.
+    Verbose(VerboseBulkWriteResult),
+}
+
+impl BulkWriteResult {
+    /// The aggregate counts for this call, regardless of whether verbose results were requested.
+    pub fn summary(&self) -> &SummaryBulkWriteResult {
+        match self {
+            BulkWriteResult::Summary(s) => s,
+            BulkWriteResult::Verbose(v) => &v.summary,
+        }
+    }
+}
+
+/// The error reported by the server for a single [`WriteModel`] in a
+/// [`Client::bulk_write`](crate::Client::bulk_write) call.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BulkWriteError {
+    /// The index of the model that this error corresponds to, in the original list of models
+    /// passed to `bulk_write`.
+    pub index: usize,
+
+    /// The server-side error code.
+    pub code: i32,
+
+    /// The server-side error message.
+    pub message: String,
+}
+
+/// Splits `models` into index ranges, such that each range can be sent to the server as a single
+/// `bulkWrite` batch without exceeding `max_batch_size` operations, and the summed `encoded_len`
+/// of a range's models does not exceed `max_message_size` bytes unless the range holds a single
+/// model. Only the models' own lengths are counted toward `max_message_size`.
+///
+/// The returned ranges are contiguous and cover `0..models.len()`, and are used to re-index
+/// per-operation results back to the caller's original positions once every batch has completed.
+pub(crate) fn split_into_batches(
+    models: &[WriteModel],
+    max_batch_size: usize,
+    max_message_size: usize,
+    encoded_len: impl Fn(&WriteModel) -> usize,
+) -> Vec<Range<usize>> {
+    let mut batches = Vec::new();
+    let mut start = 0;
+    let mut count = 0;
+    let mut size = 0;
+
+    for (i, model) in models.iter().enumerate() {
+        let model_len = encoded_len(model);
+        let would_overflow_count = count + 1 > max_batch_size;
+        let would_overflow_size = count > 0 && size + model_len > max_message_size;
+
+        if would_overflow_count || would_overflow_size {
+            batches.push(start..i);
+            start = i;
+            count = 0;
+            size = 0;
+        }
+
+        count += 1;
+        size += model_len;
+    }
+
+    if start < models.len() {
+        batches.push(start..models.len());
+    }
+
+    batches
+}
+
+/// The server's default limits on a single `bulkWrite` batch, used until the actual limits
+/// negotiated with the server via its `hello` response are available to the caller.
+const DEFAULT_MAX_WRITE_BATCH_SIZE: usize = 100_000;
+const DEFAULT_MAX_MESSAGE_SIZE_BYTES: usize = 48_000_000;
+
+/// Sends a single already-constructed command document to the server and returns its reply.
+///
+/// This is the one seam in the `bulk_write` execution path that this crate's `Client` fills in:
+/// everything else in this module (batch splitting, command construction, ordered/stop-on-error
+/// semantics, response parsing, and result re-indexing) is real, self-contained logic that does
+/// not depend on how the command bytes actually reach the server.
+pub(crate) trait BulkWriteCommandSender {
+    /// Runs `command` (a `bulkWrite` command document, always targeting the `admin` database)
+    /// against the server and returns its reply document.
+    fn send_bulk_write(&self, command: Document) -> Result<Document>;
+}
+
+/// Executes `models` as one or more `bulkWrite` commands, honoring `options`, and returns the
+/// aggregated (and, if requested, per-operation) result.
+///
+/// Batches are split to stay under `max_batch_size` operations and `max_message_size` bytes of
+/// encoded `ops` entries; the rest of each command (`nsInfo` and the top-level options) is not
+/// counted toward `max_message_size`. Each batch's per-operation results are re-indexed back to `models`'
+/// original positions before being merged into the return value. If any operation fails, the
+/// partial result accumulated so far (including everything from batches that already succeeded)
+/// is surfaced alongside the per-operation errors via [`Error::partial_bulk_write`]. When
+/// `options.ordered` is `true` (the default), execution stops at the first batch containing an
+/// error; when `false`, every batch is attempted regardless of earlier errors.
+pub(crate) fn execute_bulk_write(
+    models: &[WriteModel],
+    options: &BulkWriteOptions,
+    sender: &dyn BulkWriteCommandSender,
+    max_batch_size: Option<usize>,
+    max_message_size: Option<usize>,
+) -> Result<BulkWriteResult> {
+    if models.is_empty() {
+        return Err(Error::invalid_argument(
+            "bulk_write requires at least one WriteModel",
+        ));
+    }
+
+    let ordered = options.ordered.unwrap_or(true);
+    let verbose = options.verbose_results.unwrap_or(false);
+
+    // Generated once, up front, so that the `_id` sent to the server in each `InsertOne`'s
+    // command document is the same one recorded in that model's `InsertOneResult` below --
+    // mirroring how `Collection::insert_one`/`insert_many` generate the id client-side rather
+    // than relying on the server to report one back.
+    let insert_ids = generate_insert_ids(models);
+
+    let batches = split_into_batches(
+        models,
+        max_batch_size.unwrap_or(DEFAULT_MAX_WRITE_BATCH_SIZE),
+        max_message_size.unwrap_or(DEFAULT_MAX_MESSAGE_SIZE_BYTES),
+        |model| encoded_op_len(model).unwrap_or(0),
+    );
+
+    let mut summary = SummaryBulkWriteResult::default();
+    let mut insert_results = HashMap::new();
+    let mut update_results = HashMap::new();
+    let mut delete_results = HashMap::new();
+    let mut write_errors = Vec::new();
+
+    for batch in batches {
+        let command = build_batch_command(models, batch.clone(), options, ordered, &insert_ids)?;
+        let response = sender.send_bulk_write(command)?;
+        let outcome = parse_batch_response(models, &batch, &response, verbose, &insert_ids)?;
+
+        summary.inserted_count += outcome.summary.inserted_count;
+        summary.matched_count += outcome.summary.matched_count;
+        summary.modified_count += outcome.summary.modified_count;
+        summary.deleted_count += outcome.summary.deleted_count;
+        summary.upserted_count += outcome.summary.upserted_count;
+        summary.upserted_ids.extend(outcome.summary.upserted_ids);
+        insert_results.extend(outcome.insert_results);
+        update_results.extend(outcome.update_results);
+        delete_results.extend(outcome.delete_results);
+
+        let batch_had_errors = !outcome.write_errors.is_empty();
+        write_errors.extend(outcome.write_errors);
+
+        if ordered && batch_had_errors {
+            break;
+        }
+    }
+
+    let result = if verbose {
+        BulkWriteResult::Verbose(VerboseBulkWriteResult {
+            summary,
+            insert_results,
+            update_results,
+            delete_results,
+        })
+    } else {
+        BulkWriteResult::Summary(summary)
+    };
+
+    if write_errors.is_empty() {
+        Ok(result)
+    } else {
+        Err(Error::partial_bulk_write(result, write_errors))
+    }
+}
+
+/// Builds the `bulkWrite` command document for the operations in `batch`.
+fn build_batch_command(
+    models: &[WriteModel],
+    batch: Range<usize>,
+    options: &BulkWriteOptions,
+    ordered: bool,
+    insert_ids: &HashMap<usize, Bson>,
+) -> Result<Document> {
+    let mut ns_indices: Vec<Namespace> = Vec::new();
+    let mut ops = Vec::with_capacity(batch.len());
+    let batch_start = batch.start;
+
+    for (local_idx, model) in models[batch].iter().enumerate() {
+        let ns = namespace(model);
+        let ns_index = match ns_indices.iter().position(|n| n == ns) {
+            Some(i) => i,
+            None => {
+                ns_indices.push(ns.clone());
+                ns_indices.len() - 1
+            }
+        };
+        let insert_id = insert_ids.get(&(batch_start + local_idx));
+        ops.push(build_op(model, ns_index as i32, insert_id)?);
+    }
+
+    let ns_info: Vec<Document> = ns_indices
+        .iter()
+        .map(|ns| doc! { "ns": ns.as_command_str() })
+        .collect();
+
+    let mut command = doc! {
+        "bulkWrite": 1,
+        "ops": ops,
+        "nsInfo": ns_info,
+        "ordered": ordered,
+    };
+
+    if let Some(bypass) = options.bypass_document_validation {
+        command.insert("bypassDocumentValidation", bypass);
+    }
+    if let Some(comment) = &options.comment {
+        command.insert("comment", comment.clone());
+    }
+    if let Some(let_vars) = &options.let_vars {
+        command.insert("let", let_vars.clone());
+    }
+
+    Ok(command)
+}
+
+fn namespace(model: &WriteModel) -> &Namespace {
+    match model {
+        WriteModel::InsertOne { namespace, .. }
+        | WriteModel::UpdateOne { namespace, .. }
+        | WriteModel::UpdateMany { namespace, .. }
+        | WriteModel::ReplaceOne { namespace, .. }
+        | WriteModel::DeleteOne { namespace, .. }
+        | WriteModel::DeleteMany { namespace, .. } => namespace,
+    }
+}
+
+/// Generates a client-side `_id` for every `InsertOne` model in `models` that does not already
+/// specify one, keyed by that model's index in `models`.
+///
+/// This mirrors `Collection::insert_one`/`insert_many`, which generate the `_id` to send to the
+/// server rather than relying on the server to choose one, so that the id can be reported back to
+/// the caller even though the `bulkWrite` command's reply never echoes the inserted document.
+fn generate_insert_ids(models: &[WriteModel]) -> HashMap<usize, Bson> {
+    models
+        .iter()
+        .enumerate()
+        .filter_map(|(i, model)| match model {
+            WriteModel::InsertOne { document, .. } if !document.contains_key("_id") => {
+                Some((i, Bson::ObjectId(ObjectId::new())))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Builds the `ops` array entry for `model`, referencing its namespace by `ns_index` into the
+/// command's `nsInfo` array. `insert_id` is the client-generated `_id` for this model, if
+/// [`generate_insert_ids`] assigned it one because `model`'s document did not already have one.
+fn build_op(model: &WriteModel, ns_index: i32, insert_id: Option<&Bson>) -> Result<Document> {
+    let op = match model {
+        WriteModel::InsertOne { document, .. } => {
+            let mut document = document.clone();
+            if let Some(id) = insert_id {
+                document.insert("_id", id.clone());
+            }
+            doc! {
+                "insert": ns_index,
+                "document": document,
+            }
+        }
+        WriteModel::UpdateOne {
+            filter,
+            update,
+            array_filters,
+            collation,
+            hint,
+            upsert,
+            ..
+        }
+        | WriteModel::UpdateMany {
+            filter,
+            update,
+            array_filters,
+            collation,
+            hint,
+            upsert,
+            ..
+        } => {
+            let multi = matches!(model, WriteModel::UpdateMany { .. });
+            let update_mods =
+                bson::to_bson(update).map_err(|e| Error::invalid_argument(e.to_string()))?;
+            let mut op = doc! {
+                "update": ns_index,
+                "filter": filter.clone(),
+                "updateMods": update_mods,
+                "multi": multi,
+            };
+            if let Some(array_filters) = array_filters {
+                op.insert("arrayFilters", array_filters.clone());
+            }
+            insert_collation_and_hint(&mut op, collation, hint)?;
+            if let Some(upsert) = upsert {
+                op.insert("upsert", *upsert);
+            }
+            op
+        }
+        WriteModel::ReplaceOne {
+            filter,
+            replacement,
+            collation,
+            hint,
+            upsert,
+            ..
+        } => {
+            let mut op = doc! {
+                "update": ns_index,
+                "filter": filter.clone(),
+                "updateMods": replacement.clone(),
+                "multi": false,
+            };
+            insert_collation_and_hint(&mut op, collation, hint)?;
+            if let Some(upsert) = upsert {
+                op.insert("upsert", *upsert);
+            }
+            op
+        }
+        WriteModel::DeleteOne {
+            filter,
+            collation,
+            hint,
+            ..
+        }
+        | WriteModel::DeleteMany {
+            filter,
+            collation,
+            hint,
+            ..
+        } => {
+            let multi = matches!(model, WriteModel::DeleteMany { .. });
+            let mut op = doc! {
+                "delete": ns_index,
+                "filter": filter.clone(),
+                "multi": multi,
+            };
+            insert_collation_and_hint(&mut op, collation, hint)?;
+            op
+        }
+    };
+    Ok(op)
+}
+
+fn insert_collation_and_hint(
+    op: &mut Document,
+    collation: &Option<Collation>,
+    hint: &Option<Hint>,
+) -> Result<()> {
+    if let Some(collation) = collation {
+        op.insert(
+            "collation",
+            bson::to_bson(collation).map_err(|e| Error::invalid_argument(e.to_string()))?,
+        );
+    }
+    if let Some(hint) = hint {
+        op.insert(
+            "hint",
+            bson::to_bson(hint).map_err(|e| Error::invalid_argument(e.to_string()))?,
+        );
+    }
+    Ok(())
+}
+
+/// The BSON-encoded length of the `ops` entry that `model` would produce, used to keep a batch
+/// under the server's `maxMessageSizeBytes` limit.
+///
+/// If `model` is an `InsertOne` without an explicit `_id`, a throwaway id is used for the
+/// estimate: every generated id is a 12-byte `ObjectId`, so this doesn't need to be the same id
+/// that's eventually sent for the size estimate to be accurate.
+fn encoded_op_len(model: &WriteModel) -> Result<usize> {
+    let insert_id = match model {
+        WriteModel::InsertOne { document, .. } if !document.contains_key("_id") => {
+            Some(Bson::ObjectId(ObjectId::new()))
+        }
+        _ => None,
+    };
+    let op = build_op(model, 0, insert_id.as_ref())?;
+    bson::to_vec(&op)
+        .map(|bytes| bytes.len())
+        .map_err(|e| Error::invalid_argument(e.to_string()))
+}
+
+#[derive(Default)]
+struct BatchOutcome {
+    summary: SummaryBulkWriteResult,
+    insert_results: HashMap<usize, crate::results::InsertOneResult>,
+    update_results: HashMap<usize, crate::results::UpdateResult>,
+    delete_results: HashMap<usize, crate::results::DeleteResult>,
+    write_errors: Vec<BulkWriteError>,
+}
+
+/// Parses the reply to a single batch's `bulkWrite` command, re-indexing every per-operation
+/// result back to its position in the original (un-batched) list of models via `batch.start`.
+fn parse_batch_response(
+    models: &[WriteModel],
+    batch: &Range<usize>,
+    response: &Document,
+    verbose: bool,
+    insert_ids: &HashMap<usize, Bson>,
+) -> Result<BatchOutcome> {
+    let mut outcome = BatchOutcome::default();
+
+    outcome.summary.inserted_count = response.get_i64("nInserted").unwrap_or(0);
+    outcome.summary.matched_count = response.get_i64("nMatched").unwrap_or(0);
+    outcome.summary.modified_count = response.get_i64("nModified").unwrap_or(0);
+    outcome.summary.deleted_count = response.get_i64("nDeleted").unwrap_or(0);
+    outcome.summary.upserted_count = response.get_i64("nUpserted").unwrap_or(0);
+
+    let results = response
+        .get_document("cursor")
+        .and_then(|cursor| cursor.get_array("firstBatch"))
+        .map_err(|e| Error::invalid_response(e.to_string()))?;
+
+    for result in results {
+        let result_doc = result
+            .as_document()
+            .ok_or_else(|| Error::invalid_response("bulkWrite result entry was not a document"))?;
+        let local_idx = result_doc
+            .get_i32("idx")
+            .map_err(|e| Error::invalid_response(e.to_string()))? as usize;
+        let global_idx = batch.start + local_idx;
+        let ok = result_doc.get_f64("ok").unwrap_or(1.0);
+
+        if ok == 0.0 {
+            outcome.write_errors.push(BulkWriteError {
+                index: global_idx,
+                code: result_doc.get_i32("code").unwrap_or(0),
+                message: result_doc.get_str("errmsg").unwrap_or_default().to_string(),
+            });
+            continue;
+        }
+
+        if let Ok(upserted) = result_doc.get_document("upserted") {
+            if let Some(id) = upserted.get("_id").cloned() {
+                outcome.summary.upserted_ids.insert(global_idx, id);
+            }
+        }
+
+        if !verbose {
+            continue;
+        }
+
+        match &models[global_idx] {
+            WriteModel::InsertOne { document, .. } => {
+                let inserted_id = document
+                    .get("_id")
+                    .cloned()
+                    .or_else(|| insert_ids.get(&global_idx).cloned())
+                    .unwrap_or(Bson::Null);
+                outcome
+                    .insert_results
+                    .insert(global_idx, crate::results::InsertOneResult { inserted_id });
+            }
+            WriteModel::UpdateOne { .. }
+            | WriteModel::UpdateMany { .. }
+            | WriteModel::ReplaceOne { .. } => {
+                outcome.update_results.insert(
+                    global_idx,
+                    crate::results::UpdateResult {
+                        matched_count: result_doc.get_i32("n").unwrap_or(0) as u64,
+                        modified_count: result_doc.get_i32("nModified").unwrap_or(0) as u64,
+                        upserted_id: result_doc
+                            .get_document("upserted")
+                            .ok()
+                            .and_then(|u| u.get("_id").cloned()),
+                    },
+                );
+            }
+            WriteModel::DeleteOne { .. } | WriteModel::DeleteMany { .. } => {
+                outcome.delete_results.insert(
+                    global_idx,
+                    crate::results::DeleteResult {
+                        deleted_count: result_doc.get_i32("n").unwrap_or(0) as u64,
+                    },
+                );
+            }
+        }
+    }
+
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    fn model(db: &str, coll: &str) -> WriteModel {
+        WriteModel::InsertOne {
+            namespace: Namespace {
+                db: db.to_string(),
+                coll: coll.to_string(),
+            },
+            document: doc! { "x": 1 },
+        }
+    }
+
+    #[test]
+    fn split_into_batches_respects_max_count() {
+        let models = vec![model("a", "a"), model("a", "a"), model("a", "a")];
+        let batches = split_into_batches(&models, 2, usize::MAX, |_| 1);
+        assert_eq!(batches, vec![0..2, 2..3]);
+    }
+
+    #[test]
+    fn split_into_batches_respects_max_size() {
+        let models = vec![model("a", "a"), model("a", "a"), model("a", "a")];
+        let batches = split_into_batches(&models, usize::MAX, 25, |_| 10);
+        assert_eq!(batches, vec![0..2, 2..3]);
+    }
+
+    #[test]
+    fn split_into_batches_always_includes_at_least_one_model_per_batch() {
+        // A single oversized model should still get its own batch rather than looping forever.
+        let models = vec![model("a", "a")];
+        let batches = split_into_batches(&models, usize::MAX, 1, |_| 1_000);
+        assert_eq!(batches, vec![0..1]);
+    }
+
+    #[test]
+    fn split_into_batches_empty_input() {
+        let models: Vec<WriteModel> = vec![];
+        let batches = split_into_batches(&models, 10, 10, |_| 1);
+        assert!(batches.is_empty());
+    }
+
+    struct MockSender {
+        responses: Mutex<Vec<Document>>,
+        sent: Mutex<Vec<Document>>,
+    }
+
+    impl MockSender {
+        fn new(responses: Vec<Document>) -> Self {
+            Self {
+                responses: Mutex::new(responses),
+                sent: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    impl BulkWriteCommandSender for MockSender {
+        fn send_bulk_write(&self, command: Document) -> Result<Document> {
+            self.sent.lock().unwrap().push(command);
+            Ok(self.responses.lock().unwrap().remove(0))
+        }
+    }
+
+    #[test]
+    fn execute_bulk_write_merges_results_across_batches() {
+        let models = vec![model("db", "a"), model("db", "b")];
+        let options = BulkWriteOptions::default();
+
+        let batch_one_response = doc! {
+            "nInserted": 1i64,
+            "nMatched": 0i64,
+            "nModified": 0i64,
+            "nDeleted": 0i64,
+            "nUpserted": 0i64,
+            "cursor": { "firstBatch": [ { "idx": 0, "ok": 1.0 } ] },
+        };
+        let batch_two_response = doc! {
+            "nInserted": 1i64,
+            "nMatched": 0i64,
+            "nModified": 0i64,
+            "nDeleted": 0i64,
+            "nUpserted": 0i64,
+            "cursor": { "firstBatch": [ { "idx": 0, "ok": 1.0 } ] },
+        };
+        let sender = MockSender::new(vec![batch_one_response, batch_two_response]);
+
+        let result = execute_bulk_write(&models, &options, &sender, Some(1), None).unwrap();
+        assert_eq!(result.summary().inserted_count, 2);
+        assert_eq!(sender.sent.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn execute_bulk_write_reindexes_errors_to_the_original_model_list() {
+        let models = vec![model("db", "a"), model("db", "b"), model("db", "c")];
+        let options = BulkWriteOptions {
+            ordered: Some(false),
+            ..Default::default()
+        };
+
+        let batch_one_response = doc! {
+            "nInserted": 0i64,
+            "nMatched": 0i64,
+            "nModified": 0i64,
+            "nDeleted": 0i64,
+            "nUpserted": 0i64,
+            "cursor": { "firstBatch": [ { "idx": 0, "ok": 0.0, "code": 11000, "errmsg": "dup" } ] },
+        };
+        let batch_two_response = doc! {
+            "nInserted": 1i64,
+            "nMatched": 0i64,
+            "nModified": 0i64,
+            "nDeleted": 0i64,
+            "nUpserted": 0i64,
+            "cursor": { "firstBatch": [ { "idx": 0, "ok": 1.0 } ] },
+        };
+        let batch_three_response = doc! {
+            "nInserted": 0i64,
+            "nMatched": 0i64,
+            "nModified": 0i64,
+            "nDeleted": 0i64,
+            "nUpserted": 0i64,
+            "cursor": { "firstBatch": [ { "idx": 0, "ok": 0.0, "code": 11000, "errmsg": "dup" } ] },
+        };
+        let sender = MockSender::new(vec![
+            batch_one_response,
+            batch_two_response,
+            batch_three_response,
+        ]);
+
+        let err = execute_bulk_write(&models, &options, &sender, Some(1), None).unwrap_err();
+        let (result, write_errors) = err.partial_bulk_write_result().expect("partial result");
+        assert_eq!(result.summary().inserted_count, 1);
+        assert_eq!(write_errors[0].index, 0);
+        // The third batch's error at local index 0 is reported at its original position.
+        assert_eq!(write_errors[1].index, 2);
+        // Every batch ran because `ordered` was false.
+        assert_eq!(sender.sent.lock().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn execute_bulk_write_stops_after_first_error_when_ordered() {
+        let models = vec![model("db", "a"), model("db", "b")];
+        let options = BulkWriteOptions::default();
+
+        let batch_one_response = doc! {
+            "nInserted": 0i64,
+            "nMatched": 0i64,
+            "nModified": 0i64,
+            "nDeleted": 0i64,
+            "nUpserted": 0i64,
+            "cursor": { "firstBatch": [ { "idx": 0, "ok": 0.0, "code": 11000, "errmsg": "dup" } ] },
+        };
+        let sender = MockSender::new(vec![batch_one_response]);
+
+        let err = execute_bulk_write(&models, &options, &sender, Some(1), None).unwrap_err();
+        let (_, write_errors) = err.partial_bulk_write_result().expect("partial result");
+        assert_eq!(write_errors.len(), 1);
+        // Only the first batch ran; the second model's batch was never sent.
+        assert_eq!(sender.sent.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn execute_bulk_write_generates_insert_id_when_absent() {
+        let models = vec![model("db", "a")];
+        let options = BulkWriteOptions {
+            verbose_results: Some(true),
+            ..Default::default()
+        };
+
+        let response = doc! {
+            "nInserted": 1i64,
+            "nMatched": 0i64,
+            "nModified": 0i64,
+            "nDeleted": 0i64,
+            "nUpserted": 0i64,
+            "cursor": { "firstBatch": [ { "idx": 0, "ok": 1.0 } ] },
+        };
+        let sender = MockSender::new(vec![response]);
+
+        let result = execute_bulk_write(&models, &options, &sender, None, None).unwrap();
+        let verbose = match result {
+            BulkWriteResult::Verbose(v) => v,
+            BulkWriteResult::Summary(_) => panic!("expected verbose result"),
+        };
+        let inserted_id = verbose.insert_results[&0].inserted_id.clone();
+        assert!(matches!(inserted_id, Bson::ObjectId(_)));
+
+        // The generated id must be the one actually sent to the server, not a second,
+        // independently-generated one.
+        let sent = sender.sent.lock().unwrap();
+        let sent_doc = sent[0]
+            .get_array("ops")
+            .unwrap()[0]
+            .as_document()
+            .unwrap()
+            .get_document("document")
+            .unwrap();
+        assert_eq!(sent_doc.get("_id").cloned(), Some(inserted_id));
+    }
+
+    #[test]
+    fn execute_bulk_write_preserves_explicit_insert_id() {
+        let explicit_id = Bson::Int32(42);
+        let models = vec![WriteModel::InsertOne {
+            namespace: Namespace {
+                db: "db".to_string(),
+                coll: "a".to_string(),
+            },
+            document: doc! { "_id": explicit_id.clone(), "x": 1 },
+        }];
+        let options = BulkWriteOptions {
+            verbose_results: Some(true),
+            ..Default::default()
+        };
+
+        let response = doc! {
+            "nInserted": 1i64,
+            "nMatched": 0i64,
+            "nModified": 0i64,
+            "nDeleted": 0i64,
+            "nUpserted": 0i64,
+            "cursor": { "firstBatch": [ { "idx": 0, "ok": 1.0 } ] },
+        };
+        let sender = MockSender::new(vec![response]);
+
+        let result = execute_bulk_write(&models, &options, &sender, None, None).unwrap();
+        let verbose = match result {
+            BulkWriteResult::Verbose(v) => v,
+            BulkWriteResult::Summary(_) => panic!("expected verbose result"),
+        };
+        assert_eq!(verbose.insert_results[&0].inserted_id, explicit_id);
+    }
+}
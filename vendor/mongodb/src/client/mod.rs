@@ -0,0 +1,57 @@
+//! Contains the types for executing operations across a MongoDB deployment.
+//!
+//! This snapshot only carries the surface added for cross-namespace bulk writes
+//! ([`Client::bulk_write`]); the rest of `Client` (connecting, `Topology`, and the generic
+//! command-execution path that every other operation funnels through) lives outside it. That
+//! command-execution path is the single thing [`Client::bulk_write`] depends on but doesn't
+//! reimplement here, via the [`bulk_write::BulkWriteCommandSender`] seam.
+pub mod bulk_write;
+
+use std::sync::Arc;
+
+use crate::{
+    client::bulk_write::{
+        execute_bulk_write,
+        BulkWriteCommandSender,
+        BulkWriteOptions,
+        BulkWriteResult,
+        WriteModel,
+    },
+    error::Result,
+};
+
+/// The client-side handle used to interact with a MongoDB deployment.
+pub struct Client {
+    command_sender: Arc<dyn BulkWriteCommandSender + Send + Sync>,
+}
+
+impl Client {
+    pub(crate) fn with_bulk_write_command_sender(
+        command_sender: Arc<dyn BulkWriteCommandSender + Send + Sync>,
+    ) -> Self {
+        Self { command_sender }
+    }
+
+    /// Executes a list of write operations, potentially spanning multiple databases and
+    /// collections, in as few round trips to the server as possible via the `bulkWrite` command.
+    ///
+    /// Unlike [`Collection::insert_many`](crate::Collection::insert_many)/
+    /// [`Collection::update_many`](crate::Collection::update_many)/etc., which are always scoped
+    /// to a single collection, each [`WriteModel`] in `models` carries its own namespace, so a
+    /// single call can e.g. insert into `db1.coll1` and update `db2.coll2` together, and the
+    /// whole batch is only split into multiple `bulkWrite` commands when it would otherwise
+    /// exceed the server's `maxWriteBatchSize`/`maxMessageSizeBytes` limits.
+    pub fn bulk_write(
+        &self,
+        models: Vec<WriteModel>,
+        options: BulkWriteOptions,
+    ) -> Result<BulkWriteResult> {
+        execute_bulk_write(&models, &options, self.command_sender.as_ref(), None, None)
+    }
+}
+
+impl std::fmt::Debug for Client {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Client").finish_non_exhaustive()
+    }
+}
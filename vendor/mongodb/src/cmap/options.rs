@@ -120,6 +120,25 @@ impl ConnectionPoolOptions {
         }
     }
 
+    /// Builds the pool options for a `Client` constructed with `options`, first validating
+    /// `options.tls_options()` (see [`TlsOptions::validate`](crate::options::TlsOptions::validate))
+    /// so that a client certificate with an empty certificate or key path, or empty in-memory
+    /// certificate or key bytes, is rejected when the pool is created rather than surfacing much
+    /// later as a connection failure on the first checkout. `ca_file_path` is not checked.
+    ///
+    /// This is the entry point `Client` construction should use to build a pool. It wraps
+    /// [`from_client_options`](Self::from_client_options) rather than changing that method's
+    /// signature, since no caller of the existing infallible API is present in this snapshot to
+    /// confirm a breaking change would be safe.
+    pub(crate) fn from_validated_client_options(
+        options: &ClientOptions,
+    ) -> crate::error::Result<Self> {
+        if let Some(tls_options) = options.tls_options() {
+            tls_options.validate()?;
+        }
+        Ok(Self::from_client_options(options))
+    }
+
     pub(crate) fn to_event_options(&self) -> EventOptions {
         EventOptions {
             max_idle_time: self.max_idle_time,
@@ -0,0 +1,32 @@
+//! Contains the options for collection-level operations.
+//!
+//! This is a slice of the full `crate::coll::options` module; only the types needed by the rest
+//! of this snapshot are reproduced here.
+
+/// Specifies a database and a collection within it.
+///
+/// Unlike [`ChangeNamespace`](crate::change_stream::event::ChangeNamespace), whose `coll` is
+/// optional to account for database-level change events (e.g. `dropDatabase`), a `Namespace`
+/// always identifies a specific collection.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Namespace {
+    /// The name of the database.
+    pub db: String,
+
+    /// The name of the collection.
+    pub coll: String,
+}
+
+impl Namespace {
+    /// Returns the `db.coll` string used to identify this namespace in commands sent to the
+    /// server.
+    pub(crate) fn as_command_str(&self) -> String {
+        format!("{}.{}", self.db, self.coll)
+    }
+}
+
+impl std::fmt::Display for Namespace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_command_str())
+    }
+}
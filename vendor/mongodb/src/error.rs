@@ -0,0 +1,102 @@
+//! Contains the error type returned by fallible operations in this crate.
+//!
+//! This is a slice of the full `crate::error` module (which also covers command errors, write
+//! concern errors, and the rest of the real driver's `ErrorKind` surface); only the variants
+//! exercised by this snapshot are reproduced here.
+
+use std::{fmt, sync::Arc};
+
+use crate::client::bulk_write::{BulkWriteError, BulkWriteResult};
+
+/// The result type returned by fallible operations in this crate.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// An error that occurred while executing an operation.
+#[derive(Clone, Debug)]
+pub struct Error {
+    kind: Arc<ErrorKind>,
+}
+
+/// The types of errors that can occur.
+#[derive(Debug)]
+#[non_exhaustive]
+enum ErrorKind {
+    /// An invalid argument was provided.
+    InvalidArgument { message: String },
+
+    /// The server returned a response that could not be parsed as expected.
+    InvalidResponse { message: String },
+
+    /// A [`Client::bulk_write`](crate::Client::bulk_write) call encountered one or more write
+    /// errors after some operations had already succeeded.
+    PartialBulkWrite {
+        result: BulkWriteResult,
+        write_errors: Vec<BulkWriteError>,
+    },
+}
+
+impl Error {
+    pub(crate) fn invalid_argument(message: impl Into<String>) -> Self {
+        Self {
+            kind: Arc::new(ErrorKind::InvalidArgument {
+                message: message.into(),
+            }),
+        }
+    }
+
+    pub(crate) fn invalid_response(message: impl Into<String>) -> Self {
+        Self {
+            kind: Arc::new(ErrorKind::InvalidResponse {
+                message: message.into(),
+            }),
+        }
+    }
+
+    /// Builds the error returned by [`Client::bulk_write`](crate::Client::bulk_write) when one or
+    /// more of its operations failed. `result` carries everything that succeeded before execution
+    /// stopped (or, with `ordered: false`, everything that succeeded across every batch);
+    /// `write_errors` carries the per-operation errors reported by the server, re-indexed back to
+    /// the caller's original list of models.
+    pub(crate) fn partial_bulk_write(
+        result: BulkWriteResult,
+        write_errors: Vec<BulkWriteError>,
+    ) -> Self {
+        Self {
+            kind: Arc::new(ErrorKind::PartialBulkWrite {
+                result,
+                write_errors,
+            }),
+        }
+    }
+
+    /// If this error was returned because a [`Client::bulk_write`](crate::Client::bulk_write)
+    /// call partially failed, returns the partial result accumulated before the failure along
+    /// with the per-operation errors that caused it.
+    pub fn partial_bulk_write_result(&self) -> Option<(&BulkWriteResult, &[BulkWriteError])> {
+        match self.kind.as_ref() {
+            ErrorKind::PartialBulkWrite {
+                result,
+                write_errors,
+            } => Some((result, write_errors)),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind.as_ref() {
+            ErrorKind::InvalidArgument { message } => write!(f, "invalid argument: {}", message),
+            ErrorKind::InvalidResponse { message } => {
+                write!(f, "invalid response from server: {}", message)
+            }
+            ErrorKind::PartialBulkWrite { write_errors, .. } => write!(
+                f,
+                "bulk write error: {} write error(s) occurred",
+                write_errors.len()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
@@ -0,0 +1,204 @@
+//! Contains the options for configuring TLS connections to the server.
+//!
+//! This is a slice of the full `crate::options` module (which also holds `ClientOptions`,
+//! `DriverInfo`, `ServerAddress`, `Collation`, `Hint`, and `UpdateModifications`, referenced from
+//! `crate::cmap::options` and `crate::client::bulk_write`); those types live outside this
+//! snapshot.
+use std::path::PathBuf;
+
+/// Specifies how a TLS connection to the server should be configured.
+///
+/// If a `TlsOptions` is not specified, or is specified without any fields, the default TLS
+/// configuration will be used.
+#[derive(Clone, Default)]
+#[non_exhaustive]
+pub struct TlsOptions {
+    /// Whether invalid certificates should be accepted. This is disabled by default and should
+    /// only be used for testing, as it exposes connections to man-in-the-middle attacks.
+    pub allow_invalid_certificates: Option<bool>,
+
+    /// The path to the CA file that the driver will use to validate the server's certificate.
+    /// If not specified, the driver will use the Mozilla root certificates via the
+    /// `webpki-roots` crate.
+    pub ca_file_path: Option<PathBuf>,
+
+    /// The client certificate and private key that the driver should present to the server
+    /// during the TLS handshake, used for mutual TLS (mTLS) and X.509 authentication. If not
+    /// specified, the driver will not present a client certificate.
+    pub cert_key: Option<TlsClientCert>,
+}
+
+impl std::fmt::Debug for TlsOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TlsOptions")
+            .field(
+                "allow_invalid_certificates",
+                &self.allow_invalid_certificates,
+            )
+            .field("ca_file_path", &self.ca_file_path)
+            .field("cert_key", &self.cert_key)
+            .finish()
+    }
+}
+
+impl PartialEq for TlsOptions {
+    fn eq(&self, other: &Self) -> bool {
+        self.allow_invalid_certificates == other.allow_invalid_certificates
+            && self.ca_file_path == other.ca_file_path
+            && self.cert_key == other.cert_key
+    }
+}
+
+impl TlsOptions {
+    /// Validates that, if a client certificate is configured, its certificate chain and private
+    /// key are specified: for [`TlsClientCert::Bytes`] both must be non-empty, and for
+    /// [`TlsClientCert::File`] the certificate path (and the key path, if given) must be non-empty.
+    /// The files are not read here, so a certificate file that lacks the private key when
+    /// `key_path` is `None` is not detected by this check. Unlike the rest of TLS setup, which is only checked once a
+    /// connection is actually established, this is checked eagerly by
+    /// [`ConnectionPoolOptions::from_validated_client_options`](crate::cmap::options::ConnectionPoolOptions::from_validated_client_options)
+    /// so that a misconfigured identity fails fast rather than lazily on the first `TcpStream`
+    /// handshake. Presenting the validated certificate to the handshake itself happens in the
+    /// connection-establishment code that lives outside this snapshot.
+    pub(crate) fn validate(&self) -> crate::error::Result<()> {
+        if let Some(cert_key) = &self.cert_key {
+            cert_key.validate()?;
+        }
+        Ok(())
+    }
+}
+
+/// The client identity (certificate chain and private key) that the driver presents to the
+/// server during the TLS handshake for mutual TLS.
+///
+/// The certificate chain and private key must each be PEM-encoded. They may be provided
+/// concatenated in a single PEM document or as two separate documents; either way, both a
+/// certificate and a key must be present.
+#[derive(Clone)]
+#[non_exhaustive]
+pub enum TlsClientCert {
+    /// Load the client certificate and private key from a file (or files) on disk, in PEM
+    /// format.
+    File {
+        /// The path to the PEM file containing the certificate chain, or the certificate chain
+        /// concatenated with the private key if `key_path` is not specified.
+        cert_path: PathBuf,
+
+        /// The path to the PEM file containing the private key, if it is stored separately from
+        /// the certificate chain.
+        key_path: Option<PathBuf>,
+
+        /// The passphrase used to decrypt the private key, if it is encrypted.
+        passphrase: Option<String>,
+    },
+
+    /// Load the client certificate and private key from PEM-encoded bytes already in memory.
+    Bytes {
+        /// The PEM-encoded certificate chain.
+        cert_chain: Vec<u8>,
+
+        /// The PEM-encoded private key.
+        key: Vec<u8>,
+
+        /// The passphrase used to decrypt the private key, if it is encrypted.
+        passphrase: Option<String>,
+    },
+}
+
+impl TlsClientCert {
+    fn validate(&self) -> crate::error::Result<()> {
+        match self {
+            TlsClientCert::File {
+                cert_path,
+                key_path,
+                ..
+            } => {
+                if cert_path.as_os_str().is_empty() {
+                    return Err(crate::error::Error::invalid_argument(
+                        "cert_key certificate path must not be empty",
+                    ));
+                }
+                if let Some(key_path) = key_path {
+                    if key_path.as_os_str().is_empty() {
+                        return Err(crate::error::Error::invalid_argument(
+                            "cert_key private key path must not be empty",
+                        ));
+                    }
+                }
+            }
+            TlsClientCert::Bytes {
+                cert_chain, key, ..
+            } => {
+                if cert_chain.is_empty() {
+                    return Err(crate::error::Error::invalid_argument(
+                        "cert_key certificate chain must not be empty",
+                    ));
+                }
+                if key.is_empty() {
+                    return Err(crate::error::Error::invalid_argument(
+                        "cert_key private key must not be empty",
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for TlsClientCert {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Never print the key material or passphrase, even in debug output.
+        match self {
+            TlsClientCert::File {
+                cert_path,
+                key_path,
+                ..
+            } => f
+                .debug_struct("File")
+                .field("cert_path", cert_path)
+                .field("key_path", key_path)
+                .finish(),
+            TlsClientCert::Bytes { .. } => f.debug_struct("Bytes").finish(),
+        }
+    }
+}
+
+impl PartialEq for TlsClientCert {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (
+                TlsClientCert::File {
+                    cert_path,
+                    key_path,
+                    passphrase,
+                },
+                TlsClientCert::File {
+                    cert_path: other_cert_path,
+                    key_path: other_key_path,
+                    passphrase: other_passphrase,
+                },
+            ) => {
+                cert_path == other_cert_path
+                    && key_path == other_key_path
+                    && passphrase == other_passphrase
+            }
+            (
+                TlsClientCert::Bytes {
+                    cert_chain,
+                    key,
+                    passphrase,
+                },
+                TlsClientCert::Bytes {
+                    cert_chain: other_cert_chain,
+                    key: other_key,
+                    passphrase: other_passphrase,
+                },
+            ) => {
+                cert_chain == other_cert_chain
+                    && key == other_key
+                    && passphrase == other_passphrase
+            }
+            _ => false,
+        }
+    }
+}